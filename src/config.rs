@@ -0,0 +1,92 @@
+use crate::{Cli, Opts};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Deserialize)]
+struct LeptosToml {
+    #[serde(rename = "index-file", default = "default_index_file")]
+    index_file: PathBuf,
+    #[serde(rename = "site-addr", default = "default_site_addr")]
+    site_addr: SocketAddr,
+    #[serde(rename = "site-pkg-dir", default = "default_site_pkg_dir")]
+    site_pkg_dir: String,
+    #[serde(rename = "assets-dir", default)]
+    assets_dir: Option<PathBuf>,
+    #[serde(rename = "output-name", default)]
+    output_name: Option<String>,
+    #[serde(rename = "export-routes", default)]
+    export_routes: Vec<String>,
+}
+
+fn default_index_file() -> PathBuf {
+    PathBuf::from("index.html")
+}
+fn default_site_addr() -> SocketAddr {
+    "127.0.0.1:3000".parse().unwrap()
+}
+fn default_site_pkg_dir() -> String {
+    "pkg".to_string()
+}
+
+/// The subset of `leptos.toml` cargo-leptos needs to build, serve and export a project.
+#[derive(Debug, Clone)]
+pub struct LeptosConfig {
+    pub index_file: PathBuf,
+    pub site_addr: SocketAddr,
+    pub site_pkg_dir: String,
+    pub assets_dir: Option<PathBuf>,
+    pub output_name: String,
+    /// Routes pre-rendered by `cargo leptos export`, read from `export-routes` in `leptos.toml`.
+    pub export_routes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cli: Opts,
+    pub leptos: LeptosConfig,
+    /// Name of the server binary, read from `Cargo.toml`'s `[package] name`.
+    pub bin_name: String,
+    /// Directory cargo-leptos watches for source changes.
+    pub source_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct CargoToml {
+    package: CargoPackage,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+}
+
+pub fn read(_args: &Cli, cli: Opts) -> Result<Config> {
+    let bin_name = std::fs::read_to_string("Cargo.toml")
+        .ok()
+        .and_then(|s| toml::from_str::<CargoToml>(&s).ok())
+        .map(|c| c.package.name)
+        .unwrap_or_else(|| "app".to_string());
+
+    let leptos_toml = std::fs::read_to_string("leptos.toml")
+        .context("reading leptos.toml")
+        .and_then(|s| toml::from_str::<LeptosToml>(&s).context("parsing leptos.toml"))
+        .unwrap_or_else(|_| toml::from_str("").expect("empty leptos.toml defaults"));
+
+    let leptos = LeptosConfig {
+        index_file: leptos_toml.index_file,
+        site_addr: leptos_toml.site_addr,
+        site_pkg_dir: leptos_toml.site_pkg_dir,
+        assets_dir: leptos_toml.assets_dir,
+        output_name: leptos_toml.output_name.unwrap_or_else(|| bin_name.clone()),
+        export_routes: leptos_toml.export_routes,
+    };
+
+    Ok(Config {
+        cli,
+        leptos,
+        bin_name,
+        source_dir: PathBuf::from("src"),
+    })
+}
@@ -0,0 +1,435 @@
+//! Tracks `view!` macro invocations across watched source files so that edits
+//! confined to markup can be patched into the running page instead of
+//! triggering a full `cargo`/`wasm` rebuild.
+//!
+//! [`ViewIndex`] keeps the last known token stream for each file, split into
+//! the tokens that make up `view!` macro bodies and everything else. When a
+//! file changes, [`ViewIndex::update`] re-tokenizes it: if the "everything
+//! else" tokens are unchanged, only the `view!` bodies differ, so we diff the
+//! parsed template nodes and return a patch instead of `None`. `None` means
+//! the caller must fall back to a full rebuild.
+//!
+//! `run::watch::spawn` is the sole caller: for every changed `.rs` file it
+//! runs the file through [`ViewIndex::update`] and broadcasts either
+//! `Msg::PatchView` (markup-only edit, JSON patch attached) or
+//! `Msg::SrcChanged` (anything else) on `MSG_BUS`.
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use serde::Serialize;
+use syn::visit::Visit;
+
+/// Identifies a single `view!` invocation: its file and its ordinal position
+/// within that file (the Nth `view!` macro encountered during a left-to-right
+/// walk of the token tree).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ViewLocId {
+    file: PathBuf,
+    ordinal: usize,
+}
+
+impl fmt::Display for ViewLocId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.ordinal)
+    }
+}
+
+/// A JSON-serializable description of the static elements/attributes/text
+/// that changed inside one `view!` invocation. Sent to the browser runtime
+/// over the reload websocket as the payload of `Msg::PatchView`.
+#[derive(Debug, Serialize)]
+pub struct ViewPatch {
+    pub loc: String,
+    pub nodes: Vec<NodePatch>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodePatch {
+    /// Dot-separated child indices locating the changed node within the view tree, e.g. "0.2.1".
+    pub path: String,
+    pub kind: NodePatchKind,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodePatchKind {
+    Text(String),
+    Attribute { name: String, value: String },
+    Element(String),
+}
+
+/// A node in the simplified tree parsed out of a `view!` body: either a tag
+/// with attributes and children, or a run of non-tag tokens (text/expression).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Element {
+        tag: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+#[derive(Default)]
+struct FileEntry {
+    /// Token stream with every `view!` macro body stripped out, used to
+    /// detect whether non-markup code changed.
+    shell: TokenStream,
+    /// Raw token streams of each `view!` body, keyed by ordinal.
+    views: Vec<TokenStream>,
+}
+
+#[derive(Default)]
+pub struct ViewIndex {
+    files: HashMap<PathBuf, FileEntry>,
+}
+
+impl ViewIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-tokenizes `path` with new contents `src`. Returns `Some(patches)` if
+    /// only `view!` bodies changed, `None` if a full rebuild is required
+    /// (first sighting of the file, a parse error, non-markup code changed,
+    /// the number of `view!` invocations changed, or no `view!` body changed
+    /// at all).
+    pub fn update(&mut self, path: &Path, src: &str) -> Option<Vec<ViewPatch>> {
+        let file = syn::parse_file(src).ok()?;
+        let (shell, views) = split_views(&file);
+
+        let prev = self.files.insert(
+            path.to_path_buf(),
+            FileEntry {
+                shell: shell.clone(),
+                views: views.clone(),
+            },
+        )?;
+
+        if prev.shell.to_string() != shell.to_string() {
+            // Non-markup code changed: nothing to patch, fall back to a full rebuild.
+            return None;
+        }
+        if prev.views.len() != views.len() {
+            return None;
+        }
+
+        let patches: Vec<ViewPatch> = prev
+            .views
+            .iter()
+            .zip(views.iter())
+            .enumerate()
+            .filter(|(_, (old, new))| old.to_string() != new.to_string())
+            .map(|(ordinal, (old, new))| {
+                let loc = ViewLocId {
+                    file: path.to_path_buf(),
+                    ordinal,
+                };
+                let old_nodes = parse_nodes(old.clone());
+                let new_nodes = parse_nodes(new.clone());
+                ViewPatch {
+                    loc: loc.to_string(),
+                    nodes: diff_node_lists(&old_nodes, &new_nodes, ""),
+                }
+            })
+            .collect();
+
+        if patches.is_empty() {
+            None
+        } else {
+            Some(patches)
+        }
+    }
+}
+
+/// Walks `file` and splits it into the token stream with all `view!` bodies
+/// removed (the "shell") and the ordered list of those bodies.
+fn split_views(file: &syn::File) -> (TokenStream, Vec<TokenStream>) {
+    struct Splitter {
+        views: Vec<TokenStream>,
+    }
+    impl<'ast> Visit<'ast> for Splitter {
+        fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+            if mac.path.is_ident("view") {
+                self.views.push(mac.tokens.clone());
+            }
+            syn::visit::visit_macro(self, mac);
+        }
+    }
+    let mut splitter = Splitter { views: Vec::new() };
+    splitter.visit_file(file);
+
+    // The shell only needs to detect whether any *non-markup* token changed,
+    // so it's the whole file re-printed with every `view!` body blanked out.
+    let shell = blank_view_bodies(quote::quote!(#file));
+    (shell, splitter.views)
+}
+
+/// Replaces the contents of every `view!(...)` invocation in `tokens` with an
+/// empty group, leaving everything else byte-for-byte as printed by `quote`.
+fn blank_view_bodies(tokens: TokenStream) -> TokenStream {
+    use proc_macro2::{Group, TokenTree};
+
+    let mut out = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match &tt {
+            TokenTree::Ident(ident) if ident == "view" => {
+                out.push(tt);
+                if let Some(TokenTree::Punct(p)) = iter.peek() {
+                    if p.as_char() == '!' {
+                        out.push(iter.next().unwrap());
+                        if let Some(TokenTree::Group(group)) = iter.peek() {
+                            let delim = group.delimiter();
+                            iter.next();
+                            out.push(TokenTree::Group(Group::new(delim, TokenStream::new())));
+                            continue;
+                        }
+                    }
+                }
+            }
+            TokenTree::Group(group) => {
+                out.push(TokenTree::Group(Group::new(
+                    group.delimiter(),
+                    blank_view_bodies(group.stream()),
+                )));
+            }
+            _ => out.push(tt),
+        }
+    }
+    TokenStream::from_iter(out)
+}
+
+/// Parses a `view!` body's tokens into a flat list of sibling [`Node`]s.
+/// Understands the subset of the `view!` syntax needed to tell elements,
+/// attributes and text/expression children apart: `<tag attr=val ...>
+/// children </tag>` and self-closing `<tag attr=val .../>`. Anything it can't
+/// make sense of (fragments, control-flow blocks) is kept as opaque `Text`.
+fn parse_nodes(tokens: TokenStream) -> Vec<Node> {
+    use proc_macro2::TokenTree;
+
+    let mut nodes = Vec::new();
+    let mut text_run = String::new();
+    let mut iter = tokens.into_iter().peekable();
+
+    macro_rules! flush_text {
+        () => {
+            if !text_run.trim().is_empty() {
+                nodes.push(Node::Text(text_run.trim().to_string()));
+            }
+            text_run.clear();
+        };
+    }
+
+    while let Some(tt) = iter.next() {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == '<' => {
+                if let Some(TokenTree::Ident(_)) = iter.peek() {
+                    flush_text!();
+                    nodes.push(parse_element(&mut iter));
+                } else {
+                    text_run.push('<');
+                }
+            }
+            other => text_run.push_str(&format!("{other} ")),
+        }
+    }
+    flush_text!();
+    nodes
+}
+
+/// Parses one `<tag attr=val ...>children</tag>` or `<tag .../>` starting
+/// just after the opening `<`, consuming through its matching closing tag.
+fn parse_element(
+    iter: &mut std::iter::Peekable<proc_macro2::token_stream::IntoIter>,
+) -> Node {
+    use proc_macro2::TokenTree;
+
+    let tag = match iter.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => String::new(),
+    };
+
+    let mut attrs = Vec::new();
+    loop {
+        match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '/' => {
+                iter.next();
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '>') {
+                    iter.next();
+                }
+                return Node::Element {
+                    tag,
+                    attrs,
+                    children: Vec::new(),
+                };
+            }
+            Some(TokenTree::Punct(p)) if p.as_char() == '>' => {
+                iter.next();
+                break;
+            }
+            Some(TokenTree::Ident(_)) => {
+                let name = match iter.next() {
+                    Some(TokenTree::Ident(ident)) => ident.to_string(),
+                    _ => unreachable!(),
+                };
+                let mut value = String::new();
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=') {
+                    iter.next();
+                    if let Some(tt) = iter.next() {
+                        value = tt.to_string();
+                    }
+                }
+                attrs.push((name, value));
+            }
+            Some(_) => {
+                iter.next();
+            }
+            None => {
+                return Node::Element {
+                    tag,
+                    attrs,
+                    children: Vec::new(),
+                };
+            }
+        }
+    }
+
+    // Collect children until the matching `</tag>`. Nested elements are parsed
+    // recursively, so each one consumes its own closing tag.
+    let mut body = Vec::new();
+    let mut children = Vec::new();
+    loop {
+        match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+                iter.next();
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '/') {
+                    iter.next();
+                    while !matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '>')
+                    {
+                        if iter.next().is_none() {
+                            break;
+                        }
+                    }
+                    iter.next();
+                    break;
+                } else if matches!(iter.peek(), Some(TokenTree::Ident(_))) {
+                    children.extend(parse_nodes(TokenStream::from_iter(std::mem::take(&mut body))));
+                    children.push(parse_element(iter));
+                } else {
+                    body.push(proc_macro2::TokenTree::Punct(proc_macro2::Punct::new(
+                        '<',
+                        proc_macro2::Spacing::Alone,
+                    )));
+                }
+            }
+            Some(_) => body.push(iter.next().unwrap()),
+            None => break,
+        }
+    }
+
+    children.extend(parse_nodes(TokenStream::from_iter(body)));
+    Node::Element {
+        tag,
+        attrs,
+        children,
+    }
+}
+
+/// Renders a [`Node`] back to real HTML markup, for the cases the patch
+/// protocol can't express positionally (a whole element/subtree replacement)
+/// and that the client runtime applies via `Element.outerHTML`/`innerHTML`.
+fn render_node(node: &Node) -> String {
+    match node {
+        Node::Text(text) => text.clone(),
+        Node::Element {
+            tag,
+            attrs,
+            children,
+        } => {
+            let attrs: String = attrs
+                .iter()
+                .map(|(name, value)| format!(" {name}={value}"))
+                .collect();
+            if children.is_empty() {
+                format!("<{tag}{attrs}/>")
+            } else {
+                let inner: String = children.iter().map(render_node).collect();
+                format!("<{tag}{attrs}>{inner}</{tag}>")
+            }
+        }
+    }
+}
+
+fn render_nodes(nodes: &[Node]) -> String {
+    nodes.iter().map(render_node).collect()
+}
+
+/// Diffs two sibling node lists positionally, appending `idx` to `prefix` to
+/// build each patch's dotted path.
+fn diff_node_lists(old: &[Node], new: &[Node], prefix: &str) -> Vec<NodePatch> {
+    // A changed child count can't be expressed as a positional diff; replace
+    // the whole parent's children with real markup instead of patching them
+    // individually.
+    if old.len() != new.len() {
+        return vec![NodePatch {
+            path: prefix.to_string(),
+            kind: NodePatchKind::Element(render_nodes(new)),
+        }];
+    }
+    let mut patches = Vec::new();
+    for (idx, pair) in old.iter().zip(new.iter()).enumerate() {
+        let path = if prefix.is_empty() {
+            idx.to_string()
+        } else {
+            format!("{prefix}.{idx}")
+        };
+        patches.extend(diff_node(pair.0, pair.1, &path));
+    }
+    patches
+}
+
+fn diff_node(old: &Node, new: &Node, path: &str) -> Vec<NodePatch> {
+    if old == new {
+        return Vec::new();
+    }
+    match (old, new) {
+        (Node::Text(_), Node::Text(new_text)) => vec![NodePatch {
+            path: path.to_string(),
+            kind: NodePatchKind::Text(new_text.clone()),
+        }],
+        (
+            Node::Element {
+                tag: old_tag,
+                attrs: old_attrs,
+                children: old_children,
+            },
+            Node::Element {
+                tag: new_tag,
+                attrs: new_attrs,
+                children: new_children,
+            },
+        ) if old_tag == new_tag => {
+            let mut patches: Vec<NodePatch> = old_attrs
+                .iter()
+                .zip(new_attrs.iter())
+                .filter(|(o, n)| o != n)
+                .map(|(_, (name, value))| NodePatch {
+                    path: path.to_string(),
+                    kind: NodePatchKind::Attribute {
+                        name: name.clone(),
+                        value: value.clone(),
+                    },
+                })
+                .collect();
+            patches.extend(diff_node_lists(old_children, new_children, path));
+            patches
+        }
+        _ => vec![NodePatch {
+            path: path.to_string(),
+            kind: NodePatchKind::Element(render_node(new)),
+        }],
+    }
+}
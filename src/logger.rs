@@ -0,0 +1,25 @@
+use crate::Log;
+
+/// Sets up env_logger, muting dependency crates unless opted into via `--log`.
+pub fn setup(verbose: u8, log: &[Log]) {
+    let level = match verbose {
+        0 => log::LevelFilter::Info,
+        1 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(log::LevelFilter::Warn);
+    builder.filter_module("cargo_leptos", level);
+
+    if log.contains(&Log::Wasm) {
+        builder.filter_module("wasm_bindgen_cli_support", level);
+        builder.filter_module("walrus", level);
+    }
+    if log.contains(&Log::Server) {
+        builder.filter_module("hyper", level);
+        builder.filter_module("axum", level);
+    }
+
+    builder.init();
+}
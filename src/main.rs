@@ -1,4 +1,5 @@
 mod config;
+mod hot_reload;
 mod logger;
 mod run;
 pub mod util;
@@ -31,6 +32,8 @@ pub enum Msg {
     AssetsChanged(Watched),
     /// sent when a style file changed
     StyleChanged,
+    /// sent when a source file changed but only markup inside `view!` macros was affected; carries a JSON patch of the changed template nodes
+    PatchView(String),
     /// messages sent to reload server (forwarded to browser)
     Reload(String),
 }
@@ -62,6 +65,18 @@ pub struct Opts {
     #[arg(long)]
     csr: bool,
 
+    /// Build using Leptos' experimental islands architecture, hydrating only island components instead of the whole tree.
+    #[arg(long)]
+    islands: bool,
+
+    /// Also run the project's Playwright end-to-end tests (from `end2end/`) against a live server. Implied if `end2end/` exists.
+    #[arg(long)]
+    e2e: bool,
+
+    /// Also emit a multi-stage Dockerfile alongside the bundle.
+    #[arg(long)]
+    docker: bool,
+
     /// Verbosity (none: info, errors & warnings, -v: verbose, --vv: very verbose).
     #[arg(short, action = clap::ArgAction::Count)]
     verbose: u8,
@@ -94,6 +109,10 @@ enum Commands {
     Serve(Opts),
     /// Serve and automatically reload when files change. Defaults to hydrate mode.
     Watch(Opts),
+    /// Build and pre-render the routes configured in `leptos.toml` to static HTML in `target/site`.
+    Export(Opts),
+    /// Collect a release build into a self-contained `target/bundle` directory, optionally with a Dockerfile.
+    Bundle(Opts),
     /// Start wizard for creating a new project (using cargo-generate)
     New(NewCommand),
 }
@@ -124,7 +143,9 @@ async fn main() -> Result<()> {
         Commands::Build(opts)
         | Commands::Serve(opts)
         | Commands::Test(opts)
-        | Commands::Watch(opts) => opts,
+        | Commands::Watch(opts)
+        | Commands::Export(opts)
+        | Commands::Bundle(opts) => opts,
     };
     logger::setup(opts.verbose, &args.log);
 
@@ -141,8 +162,10 @@ async fn main() -> Result<()> {
         Commands::Config | Commands::New(_) => panic!(),
         Commands::Build(_) => build(&config, true).await,
         Commands::Serve(_) => serve(&config).await,
-        Commands::Test(_) => cargo::test(&config).await,
+        Commands::Test(_) => test(&config).await,
         Commands::Watch(_) => watch(&config).await,
+        Commands::Export(_) => export(&config).await,
+        Commands::Bundle(_) => bundle(&config).await,
     }
 }
 
@@ -167,6 +190,9 @@ async fn build(config: &Config, copy_assets: bool) -> Result<()> {
     Ok(())
 }
 async fn build_client(config: &Config) -> Result<()> {
+    if config.cli.islands && config.cli.csr {
+        anyhow::bail!("Leptos --islands cannot be combined with --csr, islands require server-side rendering");
+    }
     sass::run(&config).await?;
 
     let html = Html::read(&config.leptos.index_file)?;
@@ -181,6 +207,77 @@ async fn build_client(config: &Config) -> Result<()> {
     Ok(())
 }
 
+async fn test(config: &Config) -> Result<()> {
+    cargo::test(&config).await?;
+
+    if config.cli.e2e || PathBuf::from("end2end").exists() {
+        test_e2e(&config).await?;
+    }
+    Ok(())
+}
+
+/// Builds, serves on `config.leptos.site_addr` and runs the project's Playwright suite in `end2end/` against it.
+async fn test_e2e(config: &Config) -> Result<()> {
+    log::info!("Leptos building for end-to-end tests");
+    build(config, true).await?;
+
+    log::info!("Leptos starting server for end-to-end tests");
+    let addr = config.leptos.site_addr;
+
+    // Reuse the same serve path `cargo leptos serve` uses (honors --release
+    // and the LEPTOS_* env it sets up), run concurrently with the test run
+    // below and stopped via MSG_BUS once the suite finishes.
+    let server = cargo::run(&config);
+    let run_tests = async {
+        // Run the suite to completion first, then always signal shutdown
+        // before returning — an early `?`/`bail!` here must not leave
+        // `cargo::run`, awaited alongside this future below, running forever.
+        let result = run_playwright(addr).await;
+        *SHUTDOWN.write().await = true;
+        MSG_BUS.send(Msg::ShutDown)?;
+        result
+    };
+
+    let (server_result, test_result): (Result<()>, Result<()>) = tokio::join!(server, run_tests);
+    test_result?;
+    server_result
+}
+
+/// Waits for `addr` to accept connections, then runs `npm ci` and the
+/// project's Playwright suite against it.
+async fn run_playwright(addr: std::net::SocketAddr) -> Result<()> {
+    for attempt in 0.. {
+        if tokio::net::TcpStream::connect(addr).await.is_ok() {
+            break;
+        }
+        if attempt > 100 {
+            anyhow::bail!("Leptos timed out waiting for the server at http://{addr} to come up");
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    log::info!("Leptos running Playwright end-to-end tests against http://{addr}");
+    let status = tokio::process::Command::new("npm")
+        .arg("ci")
+        .current_dir("end2end")
+        .status()
+        .await?;
+    if !status.success() {
+        anyhow::bail!("Leptos `npm ci` failed with {status}");
+    }
+
+    let status = tokio::process::Command::new("npx")
+        .args(["playwright", "test"])
+        .current_dir("end2end")
+        .env("END2END_BASE_URL", format!("http://{addr}"))
+        .status()
+        .await?;
+    if !status.success() {
+        anyhow::bail!("Leptos end-to-end tests failed with {status}");
+    }
+    Ok(())
+}
+
 async fn serve(config: &Config) -> Result<()> {
     build(&config, true).await?;
     if config.cli.csr {
@@ -191,7 +288,136 @@ async fn serve(config: &Config) -> Result<()> {
     }
 }
 
+/// Pre-renders the routes listed under `export-routes` in `leptos.toml` to
+/// static HTML in `target/site`, reusing the same SSR server and `Html`
+/// pipeline as `cargo leptos serve`.
+async fn export(config: &Config) -> Result<()> {
+    build(&config, true).await?;
+
+    log::info!("Leptos starting SSR server to export static routes");
+    let addr = config.leptos.site_addr;
+
+    // `cargo::run` is the same SSR server `cargo leptos serve` uses in the
+    // non-csr path; `serve::spawn` would only serve the static CSR shell and
+    // the crawl below needs actually server-rendered HTML.
+    let server = cargo::run(&config);
+    let crawl = async {
+        // As in `test_e2e`: signal shutdown after the crawl regardless of
+        // outcome, so a startup timeout or a failed route fetch can't leave
+        // `cargo::run` running forever under the `tokio::join!` below.
+        let result = crawl_routes(addr, &config.leptos.export_routes).await;
+        *SHUTDOWN.write().await = true;
+        MSG_BUS.send(Msg::ShutDown)?;
+        result
+    };
+
+    let (server_result, crawl_result): (Result<()>, Result<()>) = tokio::join!(server, crawl);
+    crawl_result?;
+    server_result
+}
+
+/// Waits for `addr` to accept connections, then fetches each route and
+/// writes it to `target/site/<route>/index.html`.
+async fn crawl_routes(addr: std::net::SocketAddr, routes: &[String]) -> Result<()> {
+    for attempt in 0.. {
+        if tokio::net::TcpStream::connect(addr).await.is_ok() {
+            break;
+        }
+        if attempt > 100 {
+            anyhow::bail!("Leptos timed out waiting for the server at http://{addr} to come up");
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    for route in routes {
+        log::info!("Leptos exporting route {route}");
+        let url = format!("http://{addr}{route}");
+        let body = reqwest::get(&url)
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let out_dir = PathBuf::from("target/site").join(route.trim_start_matches('/'));
+        tokio::fs::create_dir_all(&out_dir).await?;
+        tokio::fs::write(out_dir.join("index.html"), body).await?;
+    }
+
+    log::info!("Leptos exported {} route(s)", routes.len());
+    Ok(())
+}
+
+/// Collects a release build's server binary, `target/site` assets and the
+/// `LEPTOS_*` runtime env vars into `target/bundle`, ready to `docker build`
+/// or copy onto a host that only runs a binary plus static files.
+async fn bundle(config: &Config) -> Result<()> {
+    if !config.cli.release {
+        anyhow::bail!("Leptos bundle requires --release");
+    }
+
+    log::info!("Leptos building release bundle");
+    build(&config, true).await?;
+    // `build()` always compiles a debug server; the bundle ships the release one.
+    cargo::build(&config, true).await?;
+
+    let bundle_dir = PathBuf::from("target/bundle");
+    util::rm_dir_content(&bundle_dir).ok();
+    tokio::fs::create_dir_all(&bundle_dir).await?;
+
+    let server_binary = PathBuf::from("target/release").join(&config.bin_name);
+    tokio::fs::copy(&server_binary, bundle_dir.join(&config.bin_name)).await?;
+
+    util::copy_dir_all("target/site", bundle_dir.join("site"))?;
+
+    let env_file = format!(
+        "LEPTOS_OUTPUT_NAME={name}\nLEPTOS_SITE_ROOT=site\nLEPTOS_SITE_PKG_DIR={pkg_dir}\nLEPTOS_SITE_ADDR={addr}\nLEPTOS_ENV=PROD\n",
+        name = config.leptos.output_name,
+        pkg_dir = config.leptos.site_pkg_dir,
+        addr = config.leptos.site_addr,
+    );
+    tokio::fs::write(bundle_dir.join(".env"), env_file).await?;
+
+    if config.cli.docker {
+        // Two stages so the runtime image only ever contains the compiled
+        // binary and static assets, not the Rust/wasm toolchain used to build them.
+        let dockerfile = format!(
+            r#"FROM rust:1-bookworm AS builder
+WORKDIR /work
+COPY . .
+RUN rustup target add wasm32-unknown-unknown
+RUN cargo install cargo-leptos --locked
+RUN cargo leptos bundle --release
+
+FROM debian:bookworm-slim AS runtime
+WORKDIR /app
+COPY --from=builder /work/target/release/{bin} /app/{bin}
+COPY --from=builder /work/target/site /app/site
+ENV LEPTOS_OUTPUT_NAME={name}
+ENV LEPTOS_SITE_ROOT=site
+ENV LEPTOS_SITE_PKG_DIR={pkg_dir}
+ENV LEPTOS_SITE_ADDR=0.0.0.0:{port}
+ENV LEPTOS_ENV=PROD
+EXPOSE {port}
+ENTRYPOINT ["/app/{bin}"]
+"#,
+            bin = config.bin_name,
+            name = config.leptos.output_name,
+            pkg_dir = config.leptos.site_pkg_dir,
+            port = config.leptos.site_addr.port(),
+        );
+        tokio::fs::write(bundle_dir.join("Dockerfile"), dockerfile).await?;
+    }
+
+    log::info!("Leptos bundle written to {}", bundle_dir.display());
+    Ok(())
+}
+
 async fn watch(config: &Config) -> Result<()> {
+    // Broadcasts `Msg::SrcChanged`/`Msg::StyleChanged`, or `Msg::PatchView`
+    // instead of `Msg::SrcChanged` for a markup-only `.rs` edit (see
+    // `hot_reload::ViewIndex`, which it drives internally). `reload::spawn`
+    // forwards `Msg::PatchView` straight to the browser runtime, so the full
+    // rebuild below is skipped for that message.
     let _ = watch::spawn(config).await?;
 
     if let Some(assets_dir) = &config.leptos.assets_dir {
@@ -0,0 +1,41 @@
+use crate::config::Config;
+use crate::run::watch::Watched;
+use crate::{Msg, MSG_BUS};
+use anyhow::Result;
+use notify::RecursiveMode;
+use std::path::Path;
+
+/// Copies `config.leptos.assets_dir` into `target/site` once, e.g. before a build.
+pub fn update(config: &Config) -> Result<()> {
+    if let Some(assets_dir) = &config.leptos.assets_dir {
+        crate::util::copy_dir_all(assets_dir, "target/site")?;
+    }
+    Ok(())
+}
+
+/// Watches `assets_dir` and broadcasts `Msg::AssetsChanged` on edits.
+pub async fn spawn(assets_dir: &Path) -> Result<()> {
+    let assets_dir = assets_dir.to_path_buf();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    notify::Watcher::watch(&mut watcher, &assets_dir, RecursiveMode::Recursive)?;
+
+    tokio::task::spawn_blocking(move || {
+        let _watcher = watcher;
+        for event in rx {
+            let Ok(event) = event else { continue };
+            for path in event.paths {
+                let watched = match event.kind {
+                    notify::EventKind::Create(_) => Watched::Create(path),
+                    notify::EventKind::Remove(_) => Watched::Remove(path),
+                    notify::EventKind::Modify(_) => Watched::Write(path),
+                    _ => continue,
+                };
+                let _ = MSG_BUS.send(Msg::AssetsChanged(watched));
+            }
+        }
+    });
+
+    Ok(())
+}
+
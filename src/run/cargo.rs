@@ -0,0 +1,71 @@
+use crate::config::Config;
+use crate::Msg;
+use anyhow::{bail, Result};
+use tokio::process::Command;
+
+fn leptos_envs(config: &Config) -> Vec<(&'static str, String)> {
+    vec![
+        ("LEPTOS_OUTPUT_NAME", config.leptos.output_name.clone()),
+        (
+            "LEPTOS_SITE_ROOT",
+            "target/site".to_string(),
+        ),
+        ("LEPTOS_SITE_PKG_DIR", config.leptos.site_pkg_dir.clone()),
+        ("LEPTOS_SITE_ADDR", config.leptos.site_addr.to_string()),
+    ]
+}
+
+/// Builds the server binary. `release` is independent from `config.cli.release`
+/// so callers (e.g. `build()`, which always wants a debug server while
+/// iterating) can override it.
+pub async fn build(config: &Config, release: bool) -> Result<()> {
+    let mut args = vec!["build".to_string()];
+    if release {
+        args.push("--release".to_string());
+    }
+
+    let status = Command::new("cargo").args(&args).status().await?;
+    if !status.success() {
+        bail!("Leptos server build failed with {status}");
+    }
+    Ok(())
+}
+
+/// Runs the server binary with the `LEPTOS_*` env vars the serve path relies on,
+/// until it exits or `MSG_BUS` carries `Msg::ShutDown`/`Msg::SrcChanged`, which kill it.
+pub async fn run(config: &Config) -> Result<()> {
+    let mut args = vec!["run".to_string()];
+    if config.cli.release {
+        args.push("--release".to_string());
+    }
+
+    let mut child = Command::new("cargo")
+        .args(&args)
+        .envs(leptos_envs(config))
+        .spawn()?;
+    tokio::select! {
+        status = child.wait() => {
+            let status = status?;
+            if !status.success() {
+                bail!("Leptos server exited with {status}");
+            }
+        }
+        _ = crate::util::wait_for(&[Msg::ShutDown, Msg::SrcChanged]) => {
+            child.kill().await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn test(config: &Config) -> Result<()> {
+    let mut args = vec!["test".to_string()];
+    if config.cli.release {
+        args.push("--release".to_string());
+    }
+
+    let status = Command::new("cargo").args(&args).status().await?;
+    if !status.success() {
+        bail!("Leptos tests failed with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::config::Config;
+use crate::run::reload;
+use anyhow::Result;
+use std::path::Path;
+
+/// The project's `index.html`, used as the template for both the CSR output
+/// (`generate_html`) and the hydrated/islands entry point injected for the
+/// server-rendered build (`generate_rust`).
+pub struct Html {
+    raw: String,
+}
+
+impl Html {
+    pub fn read(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        Ok(Html { raw })
+    }
+
+    /// Writes `target/site/index.html` wired to call the wasm module's
+    /// `hydrate` entry point, for `--csr` builds.
+    pub fn generate_html(&self, config: &Config) -> Result<()> {
+        self.write_with_entry(config, "hydrate")
+    }
+
+    /// Writes `target/site/index.html` wired to call either `hydrate` (the
+    /// whole tree hydrates) or `hydrate_islands` (only `#[island]` components
+    /// hydrate, per `--islands`) for the hydrate-mode build.
+    pub fn generate_rust(&self, config: &Config) -> Result<()> {
+        let entry = if config.cli.islands {
+            "hydrate_islands"
+        } else {
+            "hydrate"
+        };
+        self.write_with_entry(config, entry)
+    }
+
+    fn write_with_entry(&self, config: &Config, entry_fn: &str) -> Result<()> {
+        let reload_addr = reload::reload_addr(config);
+        let script = format!(
+            r#"<script type="module">
+import init from "/{pkg_dir}/{output_name}.js";
+init().then((wasm) => wasm.{entry_fn}());
+</script>
+<script src="http://{reload_addr}/reload.js"></script>
+"#,
+            pkg_dir = config.leptos.site_pkg_dir,
+            output_name = config.leptos.output_name,
+        );
+
+        let html = if self.raw.contains("</body>") {
+            self.raw.replacen("</body>", &format!("{script}</body>"), 1)
+        } else {
+            format!("{}{script}", self.raw)
+        };
+
+        std::fs::create_dir_all("target/site")?;
+        std::fs::write("target/site/index.html", html)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+pub mod assets;
+pub mod cargo;
+mod html;
+pub mod new;
+pub mod reload;
+pub mod sass;
+pub mod serve;
+pub mod wasm;
+pub mod watch;
+
+pub use html::Html;
@@ -0,0 +1,33 @@
+use anyhow::{Context, Result};
+use clap::Args;
+
+/// Scaffolds a new project from a cargo-generate template.
+#[derive(Debug, Args, PartialEq)]
+pub struct NewCommand {
+    /// Name of the new project.
+    name: String,
+
+    /// Template to use, defaults to the official starter.
+    #[arg(long, default_value = "leptos-rs/start")]
+    template: String,
+}
+
+impl NewCommand {
+    pub async fn run(&self) -> Result<()> {
+        let status = tokio::process::Command::new("cargo")
+            .args([
+                "generate",
+                "--git",
+                &self.template,
+                "--name",
+                &self.name,
+            ])
+            .status()
+            .await
+            .context("running cargo-generate, is it installed? `cargo install cargo-generate`")?;
+        if !status.success() {
+            anyhow::bail!("Leptos `cargo generate` failed with {status}");
+        }
+        Ok(())
+    }
+}
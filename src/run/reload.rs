@@ -0,0 +1,109 @@
+use crate::config::Config;
+use crate::{Msg, MSG_BUS};
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use std::net::SocketAddr;
+
+/// Small client runtime injected into every generated `index.html`. Connects
+/// to the reload websocket below and either does a full `location.reload()`
+/// or, for a `Msg::PatchView` patch, swaps the text/attribute of the affected
+/// nodes in place — located by the `data-hk="<loc>.<path>"` hydration key
+/// Leptos already stamps onto server-rendered elements.
+const CLIENT_JS: &str = r#"
+(() => {
+  const ws = new WebSocket(`ws://${location.host.split(":")[0]}:__CARGO_LEPTOS_RELOAD_PORT__/ws`);
+  ws.onmessage = (ev) => {
+    const msg = JSON.parse(ev.data);
+    if (msg.type === "reload") {
+      location.reload();
+      return;
+    }
+    if (msg.type === "patch") {
+      for (const view of msg.patches) {
+        for (const node of view.nodes) {
+          const el = document.querySelector(`[data-hk="${view.loc}.${node.path}"]`);
+          if (!el) { location.reload(); return; }
+          if ("text" in node.kind) el.textContent = node.kind.text;
+          else if ("attribute" in node.kind) el.setAttribute(node.kind.attribute.name, node.kind.attribute.value);
+          else el.outerHTML = node.kind.element;
+        }
+      }
+    }
+  };
+})();
+"#;
+
+pub fn reload_addr(config: &Config) -> SocketAddr {
+    let mut addr = config.leptos.site_addr;
+    addr.set_port(addr.port() + 1);
+    addr
+}
+
+/// Starts the websocket server the browser runtime connects to, forwarding
+/// both full `Msg::Reload` and markup-only `Msg::PatchView` messages.
+pub async fn spawn(config: &Config) {
+    let addr = reload_addr(config);
+    let client_js = CLIENT_JS.replace("__CARGO_LEPTOS_RELOAD_PORT__", &addr.port().to_string());
+
+    tokio::spawn(async move {
+        let app = Router::new()
+            .route("/ws", get(ws_handler))
+            .route(
+                "/reload.js",
+                get(move || {
+                    let body = client_js.clone();
+                    async move { ([("content-type", "application/javascript")], body) }
+                }),
+            );
+
+        match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => {
+                log::debug!("Leptos reload server listening on ws://{addr}");
+                if let Err(e) = axum::serve(listener, app).await {
+                    log::error!("Leptos reload server error: {e}");
+                }
+            }
+            Err(e) => log::error!("Leptos failed to bind reload server on {addr}: {e}"),
+        }
+    });
+}
+
+async fn ws_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(handle_socket)
+}
+
+async fn handle_socket(mut socket: WebSocket) {
+    let mut rx = MSG_BUS.subscribe();
+    loop {
+        let msg = match rx.recv().await {
+            Ok(msg) => msg,
+            Err(_) => break,
+        };
+        let payload = match msg {
+            Msg::Reload(_) => serde_json::json!({ "type": "reload" }),
+            Msg::PatchView(patch) => {
+                let patches: serde_json::Value = match serde_json::from_str(&patch) {
+                    Ok(patches) => patches,
+                    Err(e) => {
+                        log::error!("Leptos failed to decode view patch: {e}");
+                        continue;
+                    }
+                };
+                serde_json::json!({ "type": "patch", "patches": patches })
+            }
+            Msg::ShutDown => break,
+            _ => continue,
+        };
+        if socket
+            .send(Message::Text(payload.to_string()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
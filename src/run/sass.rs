@@ -0,0 +1,23 @@
+use crate::config::Config;
+use anyhow::{bail, Result};
+
+/// Compiles `style/main.scss` (if present) to `target/site/<pkg-dir>/app.css`.
+pub async fn run(config: &Config) -> Result<()> {
+    let input = std::path::Path::new("style/main.scss");
+    if !input.exists() {
+        return Ok(());
+    }
+
+    let out_dir = format!("target/site/{}", config.leptos.site_pkg_dir);
+    std::fs::create_dir_all(&out_dir)?;
+    let output = format!("{out_dir}/app.css");
+
+    let status = tokio::process::Command::new("sass")
+        .args([input.to_str().unwrap(), &output])
+        .status()
+        .await?;
+    if !status.success() {
+        bail!("Leptos sass compilation failed with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::config::Config;
+use anyhow::Result;
+use std::path::PathBuf;
+use tokio::task::JoinHandle;
+
+/// Serves `target/site` as static files, for `--csr` builds that don't run a
+/// server binary of their own. Returns a handle already running in the
+/// background; callers that want to block until it exits await the handle.
+pub async fn spawn(config: &Config) -> JoinHandle<Result<()>> {
+    let addr = config.leptos.site_addr;
+    let site_root = PathBuf::from("target/site");
+
+    tokio::spawn(async move {
+        let app = axum::Router::new().nest_service(
+            "/",
+            tower_http::services::ServeDir::new(site_root),
+        );
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        log::info!("Leptos serving CSR build on http://{addr}");
+        axum::serve(listener, app).await?;
+        Ok(())
+    })
+}
@@ -0,0 +1,70 @@
+use crate::config::Config;
+use anyhow::{bail, Result};
+
+/// Compiles the client-side wasm binary and runs it through `wasm-bindgen`.
+pub async fn build(config: &Config) -> Result<()> {
+    let mut features = if config.cli.csr {
+        vec!["csr".to_string()]
+    } else {
+        vec!["hydrate".to_string()]
+    };
+    if config.cli.islands {
+        // Leptos' islands architecture: only `#[island]` components ship wasm
+        // and hydrate, everything else stays server-rendered markup.
+        features.push("experimental-islands".to_string());
+    }
+
+    let mut args = vec![
+        "build".to_string(),
+        "--lib".to_string(),
+        "--target".to_string(),
+        "wasm32-unknown-unknown".to_string(),
+        "--no-default-features".to_string(),
+        "--features".to_string(),
+        features.join(","),
+    ];
+    if config.cli.release {
+        args.push("--release".to_string());
+    }
+
+    log::debug!("Leptos building wasm with features [{}]", features.join(","));
+    let status = tokio::process::Command::new("cargo")
+        .args(&args)
+        .status()
+        .await?;
+    if !status.success() {
+        bail!("Leptos wasm build failed with {status}");
+    }
+
+    wasm_bindgen(config).await
+}
+
+/// Runs `wasm-bindgen` against the freshly built wasm binary, writing the
+/// bundle cargo-leptos serves from `target/site/<site-pkg-dir>`.
+async fn wasm_bindgen(config: &Config) -> Result<()> {
+    let profile = if config.cli.release { "release" } else { "debug" };
+    let wasm_file = format!(
+        "target/wasm32-unknown-unknown/{profile}/{}.wasm",
+        config.bin_name
+    );
+    let out_dir = format!("target/site/{}", config.leptos.site_pkg_dir);
+    std::fs::create_dir_all(&out_dir)?;
+
+    let status = tokio::process::Command::new("wasm-bindgen")
+        .args([
+            &wasm_file,
+            "--out-dir",
+            &out_dir,
+            "--out-name",
+            &config.leptos.output_name,
+            "--target",
+            "web",
+            "--no-typescript",
+        ])
+        .status()
+        .await?;
+    if !status.success() {
+        bail!("Leptos wasm-bindgen failed with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::config::Config;
+use crate::hot_reload::ViewIndex;
+use crate::{Msg, MSG_BUS};
+use anyhow::Result;
+use notify::RecursiveMode;
+use std::sync::Mutex;
+
+/// Describes which asset changed, sent as the payload of `Msg::AssetsChanged`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Watched {
+    Create(std::path::PathBuf),
+    Remove(std::path::PathBuf),
+    Write(std::path::PathBuf),
+}
+
+/// Watches `config.source_dir` for `.rs` changes and the `style` directory
+/// (if present) for style file changes. For a `.rs` edit, runs it through a `ViewIndex` first:
+/// if only `view!` markup changed, broadcasts `Msg::PatchView` with the
+/// computed patch instead of `Msg::SrcChanged`, so a markup-only edit never
+/// triggers the full rebuild in `watch()`.
+pub async fn spawn(config: &Config) -> Result<()> {
+    let source_dir = config.source_dir.clone();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    notify::Watcher::watch(&mut watcher, &source_dir, RecursiveMode::Recursive)?;
+    if let Ok(style_dir) = std::fs::canonicalize("style") {
+        notify::Watcher::watch(&mut watcher, &style_dir, RecursiveMode::Recursive).ok();
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let _watcher = watcher;
+        let index = Mutex::new(ViewIndex::new());
+
+        for event in rx {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    log::error!("Leptos watch error: {e}");
+                    continue;
+                }
+            };
+            if !matches!(
+                event.kind,
+                notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+            ) {
+                continue;
+            }
+
+            for path in event.paths {
+                let msg = match path.extension().and_then(|e| e.to_str()) {
+                    Some("rs") => match std::fs::read_to_string(&path) {
+                        Ok(src) => match index.lock().unwrap().update(&path, &src) {
+                            Some(patches) => match serde_json::to_string(&patches) {
+                                Ok(json) => Msg::PatchView(json),
+                                Err(e) => {
+                                    log::error!("Leptos failed to encode view patch: {e}");
+                                    Msg::SrcChanged
+                                }
+                            },
+                            None => Msg::SrcChanged,
+                        },
+                        Err(e) => {
+                            log::warn!("Leptos could not read {}: {e}", path.display());
+                            continue;
+                        }
+                    },
+                    Some("scss") | Some("sass") | Some("css") => Msg::StyleChanged,
+                    _ => continue,
+                };
+
+                if MSG_BUS.send(msg.clone()).is_err() {
+                    log::debug!("Leptos watch: no receivers on the message bus");
+                }
+                if msg == Msg::SrcChanged {
+                    log::debug!("Leptos source changed: {}", path.display());
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::Msg;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Blocks until `MSG_BUS` produces one of `msgs`, ignoring everything else.
+pub async fn wait_for(msgs: &[Msg]) {
+    let mut rx = crate::MSG_BUS.subscribe();
+    loop {
+        match rx.recv().await {
+            Ok(msg) if msgs.contains(&msg) => return,
+            Ok(_) => continue,
+            Err(_) => return,
+        }
+    }
+}
+
+pub trait PathBufAdditions {
+    /// Returns the path with its last component (usually a file name) removed.
+    fn without_last(&self) -> PathBuf;
+}
+
+impl PathBufAdditions for PathBuf {
+    fn without_last(&self) -> PathBuf {
+        self.parent().map(Path::to_path_buf).unwrap_or_default()
+    }
+}
+
+/// Removes everything inside `dir` without removing `dir` itself, creating it
+/// first if it doesn't exist.
+pub fn rm_dir_content(dir: impl AsRef<Path>) -> Result<()> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        std::fs::create_dir_all(dir)?;
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)?;
+        } else {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Recursively copies the contents of `src` into `dst`, creating `dst` if needed.
+pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
+    let (src, dst) = (src.as_ref(), dst.as_ref());
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let to = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(entry.path(), to)?;
+        } else {
+            std::fs::copy(entry.path(), to)?;
+        }
+    }
+    Ok(())
+}